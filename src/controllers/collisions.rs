@@ -0,0 +1,70 @@
+//! Detects and resolves collisions between bullets, asteroids and the player.
+
+use crate::controllers::{Event, TimeController};
+use crate::game_state::GameState;
+
+const ASTEROID_RADIUS: f32 = 24.0;
+const BULLET_RADIUS: f32 = 4.0;
+const PLAYER_RADIUS: f32 = 16.0;
+
+/// Resolves all collisions for the current frame: bullets destroying asteroids, and asteroids
+/// ending the player's run.
+pub struct CollisionsController;
+
+impl CollisionsController {
+    /// Checks every bullet/asteroid and asteroid/player pair and applies the result
+    pub fn handle_collisions(
+        game_state: &mut GameState,
+        _time_controller: &mut TimeController,
+        event_buffer: &mut Vec<Event>,
+    ) {
+        if game_state.message.is_some() {
+            return;
+        }
+
+        let mut destroyed_bullets = Vec::new();
+        let mut destroyed_asteroids = Vec::new();
+        for (bullet_index, bullet) in game_state.bullets.iter().enumerate() {
+            for (asteroid_index, asteroid) in game_state.asteroids.iter().enumerate() {
+                let bullet_position = bullet.model.current().position;
+                let asteroid_position = asteroid.model.current().position;
+                if distance(bullet_position, asteroid_position) < ASTEROID_RADIUS + BULLET_RADIUS {
+                    destroyed_bullets.push(bullet_index);
+                    destroyed_asteroids.push(asteroid_index);
+                    event_buffer.push(Event::Explosion {
+                        position: asteroid_position,
+                    });
+                }
+            }
+        }
+
+        for index in dedup_sorted_desc(destroyed_bullets) {
+            game_state.bullets.remove(index);
+        }
+        for index in dedup_sorted_desc(destroyed_asteroids) {
+            game_state.asteroids.remove(index);
+        }
+
+        let player_position = game_state.player.model.current().position;
+        let player_died = game_state.asteroids.iter().any(|asteroid| {
+            distance(player_position, asteroid.model.current().position)
+                < ASTEROID_RADIUS + PLAYER_RADIUS
+        });
+        if player_died {
+            event_buffer.push(Event::PlayerDeath {
+                position: player_position,
+            });
+            game_state.message = Some("You died! Press any key to restart.".to_string());
+        }
+    }
+}
+
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+fn dedup_sorted_desc(mut indices: Vec<usize>) -> Vec<usize> {
+    indices.sort_unstable_by(|a, b| b.cmp(a));
+    indices.dedup();
+    indices
+}
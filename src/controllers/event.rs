@@ -0,0 +1,37 @@
+//! Events emitted by the simulation that the view layer turns into sound effects.
+
+/// An event emitted by the simulation for the view layer to react to (typically by playing a
+/// sound). Carries the position it originated from, so the view can pan/attenuate the sound
+/// spatially.
+#[derive(Clone, Copy, Debug)]
+pub enum Event {
+    /// A fresh run has started
+    GameStart,
+    /// The player fired a shot, from this position
+    Shot {
+        /// Where the shot originated, in game coordinates
+        position: (f32, f32),
+    },
+    /// An asteroid was destroyed at this position
+    Explosion {
+        /// Where the explosion occurred, in game coordinates
+        position: (f32, f32),
+    },
+    /// The player's rocket was destroyed at this position
+    PlayerDeath {
+        /// Where the player died, in game coordinates
+        position: (f32, f32),
+    },
+}
+
+impl Event {
+    /// The position this event should be spatialized around, if any
+    pub fn position(&self) -> Option<(f32, f32)> {
+        match *self {
+            Event::GameStart => None,
+            Event::Shot { position }
+            | Event::Explosion { position }
+            | Event::PlayerDeath { position } => Some(position),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Tracks which keys/gamepad buttons are currently held and derives the current frame's
+//! abstract `Actions` from them.
+
+use std::collections::HashSet;
+
+use ggez::input::gamepad::gilrs::{Axis, Button};
+use ggez::input::keyboard::{KeyCode, KeyInput};
+use ggez::input::mouse::MouseButton;
+use serde::{Deserialize, Serialize};
+
+/// The set of abstract actions the player can be performing during a single frame, independent
+/// of which physical input device produced them.
+///
+/// `Copy` so a single frame's actions can be fed to both the recorder and the simulation step
+/// without an explicit clone, and `Serialize`/`Deserialize` so a whole run's stream of actions
+/// can be written to and read back from a replay file.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct Actions {
+    /// -1.0 (full left) ..= 1.0 (full right); keyboard taps snap to -1.0/0.0/1.0, the gamepad
+    /// stick reports proportional deflection. Ignored in favor of `desired_aim` when the mouse
+    /// is the active aiming input.
+    pub turn: f32,
+    /// Whether boost is currently held
+    pub boost: bool,
+    /// Whether fire is currently held
+    pub fire: bool,
+    /// The point (in game coordinates) the player should steer toward, if the mouse has moved
+    /// at least once; `TimeController::step` turns the player toward this instead of using
+    /// `turn` when it's set
+    pub desired_aim: Option<(f32, f32)>,
+}
+
+/// Tracks which keys/gamepad buttons/mouse buttons are currently held, and the latest cursor
+/// position, and derives the current frame's `Actions` from them.
+pub struct InputController {
+    keys_down: HashSet<KeyCode>,
+    gamepad_buttons_down: HashSet<Button>,
+    gamepad_turn: f32,
+    mouse_buttons_down: HashSet<MouseButton>,
+    cursor_position: Option<(f32, f32)>,
+}
+
+impl InputController {
+    /// Creates an input controller with nothing held down
+    pub fn new() -> InputController {
+        InputController {
+            keys_down: HashSet::new(),
+            gamepad_buttons_down: HashSet::new(),
+            gamepad_turn: 0.0,
+            mouse_buttons_down: HashSet::new(),
+            cursor_position: None,
+        }
+    }
+
+    /// Records a key as held down
+    pub fn key_press(&mut self, input: KeyInput) {
+        if let Some(keycode) = input.keycode {
+            self.keys_down.insert(keycode);
+        }
+    }
+
+    /// Records a key as released
+    pub fn key_release(&mut self, input: KeyInput) {
+        if let Some(keycode) = input.keycode {
+            self.keys_down.remove(&keycode);
+        }
+    }
+
+    /// Records a gamepad button as held down
+    pub fn gamepad_button_press(&mut self, button: Button) {
+        self.gamepad_buttons_down.insert(button);
+    }
+
+    /// Records a gamepad button as released
+    pub fn gamepad_button_release(&mut self, button: Button) {
+        self.gamepad_buttons_down.remove(&button);
+    }
+
+    /// Feeds in the latest value of the left stick's X axis, already deadzone-filtered by the
+    /// caller, mapped to proportional turn-left/turn-right
+    pub fn gamepad_axis(&mut self, axis: Axis, value: f32) {
+        if axis == Axis::LeftStickX {
+            self.gamepad_turn = value;
+        }
+    }
+
+    /// Updates the latest known cursor position, in game coordinates
+    pub fn set_cursor_position(&mut self, x: f32, y: f32) {
+        self.cursor_position = Some((x, y));
+    }
+
+    /// Records a mouse button as held down
+    pub fn mouse_button_press(&mut self, button: MouseButton) {
+        self.mouse_buttons_down.insert(button);
+    }
+
+    /// Records a mouse button as released
+    pub fn mouse_button_release(&mut self, button: MouseButton) {
+        self.mouse_buttons_down.remove(&button);
+    }
+
+    /// The abstract actions implied by everything currently held down, across keyboard,
+    /// gamepad and mouse
+    pub fn actions(&self) -> Actions {
+        let keyboard_turn = if self.keys_down.contains(&KeyCode::Left) {
+            -1.0
+        } else if self.keys_down.contains(&KeyCode::Right) {
+            1.0
+        } else {
+            0.0
+        };
+
+        Actions {
+            turn: if self.gamepad_turn != 0.0 {
+                self.gamepad_turn
+            } else {
+                keyboard_turn
+            },
+            boost: self.keys_down.contains(&KeyCode::Up)
+                || self.gamepad_buttons_down.contains(&Button::South),
+            fire: self.keys_down.contains(&KeyCode::Space)
+                || self.gamepad_buttons_down.contains(&Button::South)
+                || self.mouse_buttons_down.contains(&MouseButton::Left),
+            desired_aim: self.cursor_position,
+        }
+    }
+}
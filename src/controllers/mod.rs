@@ -0,0 +1,12 @@
+//! Controllers that advance and react to the simulation: input handling, time stepping, and
+//! collision resolution.
+
+mod collisions;
+mod event;
+mod input;
+mod time;
+
+pub use collisions::CollisionsController;
+pub use event::Event;
+pub use input::{Actions, InputController};
+pub use time::TimeController;
@@ -0,0 +1,145 @@
+//! Advances the game state by fixed timesteps, independent of the display's refresh rate.
+
+use rand::Rng;
+
+use crate::controllers::{Actions, Event};
+use crate::game_state::GameState;
+use crate::models::Transform;
+
+const TURN_SPEED: f32 = std::f32::consts::PI; // radians/sec at full deflection
+const AIM_TURN_SPEED: f32 = std::f32::consts::TAU; // radians/sec when steering toward the cursor
+const BOOST_ACCELERATION: f32 = 200.0;
+const BULLET_SPEED: f32 = 500.0;
+
+/// Accumulates real elapsed time and steps the simulation forward in fixed-size increments, so
+/// physics and collision behavior are frame-rate independent.
+pub struct TimeController {
+    accumulator: f64,
+}
+
+impl TimeController {
+    /// Creates a fresh time controller with an empty accumulator
+    pub fn new() -> TimeController {
+        TimeController { accumulator: 0.0 }
+    }
+
+    /// Resets the accumulator, e.g. when the game restarts
+    pub fn reset(&mut self) {
+        self.accumulator = 0.0;
+    }
+
+    /// Adds a frame's worth of real elapsed time to the accumulator
+    pub fn accumulate(&mut self, delta_secs: f64) {
+        self.accumulator += delta_secs;
+    }
+
+    /// How much unsimulated time is currently banked
+    pub fn accumulator(&self) -> f64 {
+        self.accumulator
+    }
+
+    /// Caps the banked backlog at `max_steps` worth of `fixed_step_secs`, so a sustained slow
+    /// frame can't make the backlog grow forever once the per-frame catch-up cap is hit
+    pub fn clamp_accumulator(&mut self, fixed_step_secs: f64, max_steps: u32) {
+        let max = fixed_step_secs * max_steps as f64;
+        if self.accumulator > max {
+            self.accumulator = max;
+        }
+    }
+
+    /// The fraction of the next fixed step already banked, for render interpolation
+    pub fn interpolation_alpha(&self, fixed_step_secs: f64) -> f32 {
+        ((self.accumulator / fixed_step_secs) as f32).min(1.0)
+    }
+
+    /// Runs a single fixed-size simulation step, consuming `fixed_step_secs` worth of banked
+    /// time
+    pub fn step<R: Rng>(
+        &mut self,
+        fixed_step_secs: f64,
+        actions: Actions,
+        game_state: &mut GameState,
+        event_buffer: &mut Vec<Event>,
+        rng: &mut R,
+    ) {
+        self.accumulator -= fixed_step_secs;
+        let _ = rng;
+
+        if game_state.message.is_some() {
+            return;
+        }
+
+        let dt = fixed_step_secs as f32;
+        let player = &mut game_state.player;
+        let current = player.model.current();
+
+        // Steer toward the mouse aim point when one is set, otherwise fall back to the
+        // discrete turn-left/turn-right action
+        let rotation = if let Some(aim) = actions.desired_aim {
+            let to_aim = (aim.1 - current.position.1).atan2(aim.0 - current.position.0);
+            let mut delta = to_aim - current.rotation;
+            while delta > std::f32::consts::PI {
+                delta -= std::f32::consts::TAU;
+            }
+            while delta < -std::f32::consts::PI {
+                delta += std::f32::consts::TAU;
+            }
+            let max_step = AIM_TURN_SPEED * dt;
+            current.rotation + delta.clamp(-max_step, max_step)
+        } else {
+            current.rotation + actions.turn * TURN_SPEED * dt
+        };
+
+        if actions.boost {
+            player.velocity.0 += rotation.cos() * BOOST_ACCELERATION * dt;
+            player.velocity.1 += rotation.sin() * BOOST_ACCELERATION * dt;
+        }
+
+        let position = (
+            crate::util::wrap(current.position.0 + player.velocity.0 * dt, game_state.size.width),
+            crate::util::wrap(
+                current.position.1 + player.velocity.1 * dt,
+                game_state.size.height,
+            ),
+        );
+        player.model.advance(Transform { position, rotation });
+
+        if actions.fire {
+            let current = player.model.current();
+            game_state.bullets.push(crate::models::Bullet {
+                model: crate::models::Model::new(current),
+                velocity: (current.rotation.cos() * BULLET_SPEED, current.rotation.sin() * BULLET_SPEED),
+            });
+            event_buffer.push(Event::Shot {
+                position: current.position,
+            });
+        }
+
+        for bullet in &mut game_state.bullets {
+            let current = bullet.model.current();
+            let position = (
+                current.position.0 + bullet.velocity.0 * dt,
+                current.position.1 + bullet.velocity.1 * dt,
+            );
+            bullet.model.advance(Transform {
+                position,
+                rotation: current.rotation,
+            });
+        }
+
+        for asteroid in &mut game_state.asteroids {
+            let current = asteroid.model.current();
+            let position = (
+                crate::util::wrap(current.position.0 + asteroid.velocity.0 * dt, game_state.size.width),
+                crate::util::wrap(
+                    current.position.1 + asteroid.velocity.1 * dt,
+                    game_state.size.height,
+                ),
+            );
+            asteroid.model.advance(Transform {
+                position,
+                rotation: current.rotation,
+            });
+        }
+    }
+}
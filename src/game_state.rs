@@ -0,0 +1,49 @@
+//! Holds all of the state needed to simulate and render a single run of the game.
+
+use rand::Rng;
+
+use geometry::Size;
+
+use crate::models::{Asteroid, Bullet, Player};
+
+const INITIAL_ASTEROID_COUNT: usize = 4;
+
+/// All of the state needed to simulate and render a single run of the game.
+pub struct GameState {
+    /// The play area's dimensions; entities wrap around at its edges
+    pub size: Size,
+    /// The player-controlled rocket
+    pub player: Player,
+    /// Asteroids currently drifting through the play area
+    pub asteroids: Vec<Asteroid>,
+    /// Bullets currently in flight
+    pub bullets: Vec<Bullet>,
+    /// Set once the player has died; while this is `Some`, input handlers wait for the player
+    /// to dismiss it before resetting
+    pub message: Option<String>,
+}
+
+impl GameState {
+    /// Creates a fresh game state sized to `size`, spawning the initial set of asteroids
+    pub fn new<R: Rng>(size: Size, rng: &mut R) -> GameState {
+        GameState {
+            size,
+            player: Player::spawn(size),
+            asteroids: Asteroid::spawn_many(INITIAL_ASTEROID_COUNT, size, rng),
+            bullets: Vec::new(),
+            message: None,
+        }
+    }
+
+    /// Resets the simulation back to its starting layout, re-seeding the play bounds from
+    /// `size` so the new run is laid out for the current window size
+    pub fn reset<R: Rng>(&mut self, size: Size, rng: &mut R) {
+        *self = GameState::new(size, rng);
+    }
+
+    /// Updates the play area's bounds without otherwise touching the simulation, so dragging
+    /// the window-size slider doesn't interrupt an in-progress run
+    pub fn resize(&mut self, size: Size) {
+        self.size = size;
+    }
+}
@@ -7,22 +7,41 @@ extern crate geometry_derive;
 mod controllers;
 mod game_state;
 mod models;
+mod replay;
 mod util;
 mod view;
 
+use std::path::PathBuf;
+
 use ggez::event;
-use ggez::input::keyboard::KeyInput;
+use ggez::input::gamepad::gilrs::{Axis, Button};
+use ggez::input::gamepad::GamepadId;
+use ggez::input::keyboard::{KeyCode, KeyInput};
+use ggez::input::mouse::MouseButton;
 use ggez::{Context, GameResult};
-use rand::prelude::ThreadRng;
+use ggez_egui::{egui, EguiBackend};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use structopt::StructOpt;
 
 use crate::{
     controllers::{CollisionsController, Event, InputController, TimeController},
     game_state::GameState,
     geometry::Size,
+    replay::{Player, Recorder},
     view::Resources,
 };
 
+// Analog stick deflection below this magnitude is treated as noise and ignored
+const GAMEPAD_AXIS_DEADZONE: f32 = 0.2;
+
+// The simulation always advances in steps of this size, independent of the display's refresh
+// rate, so physics and collision behavior are deterministic
+const FIXED_TIMESTEP_SECONDS: f64 = 1.0 / 120.0;
+// Caps how many fixed steps we run in a single `update()` call, so a slow frame can't make the
+// game spiral into running ever more simulation steps to catch up
+const MAX_CATCHUP_STEPS: u32 = 5;
+
 /// This struct contains the application's state
 pub struct ApplicationState {
     // Keep track of window focus to play/pause the game
@@ -38,14 +57,32 @@ pub struct ApplicationState {
     // The event buffer keeps track of events that trigger sounds, so we can separate
     // sound playing from the game logic
     event_buffer: Vec<Event>,
-    // A source of randomness
-    rng: ThreadRng,
+    // A source of randomness, seeded so that runs are reproducible
+    rng: StdRng,
+    // The egui integration used to draw the pause menu on top of the game
+    egui_backend: EguiBackend,
+    // Whether the pause menu is currently shown; while paused the simulation does not advance
+    paused: bool,
+    // The master volume slider in the pause menu feeds into `view::play_sounds`
+    master_volume: f32,
+    // The game window size, adjustable from the pause menu
+    game_size: Size,
+    // When set, every frame's actions and delta are appended here for later replay
+    recorder: Option<Recorder>,
+    // When set, frames are pulled from here instead of from live input
+    player: Option<Player>,
 }
 
 impl ApplicationState {
     /// Simply creates a new application state
-    fn new(ctx: &mut Context, game_size: Size) -> GameResult<ApplicationState> {
-        let mut rng = rand::thread_rng();
+    fn new(
+        ctx: &mut Context,
+        game_size: Size,
+        seed: u64,
+        recorder: Option<Recorder>,
+        player: Option<Player>,
+    ) -> GameResult<ApplicationState> {
+        let mut rng = StdRng::seed_from_u64(seed);
         let app_state = ApplicationState {
             has_focus: true,
             resources: Resources::new(ctx),
@@ -54,6 +91,12 @@ impl ApplicationState {
             input_controller: InputController::new(),
             event_buffer: Vec::new(),
             rng,
+            egui_backend: EguiBackend::default(),
+            paused: false,
+            master_volume: 1.0,
+            game_size,
+            recorder,
+            player,
         };
         Ok(app_state)
     }
@@ -63,11 +106,47 @@ impl ApplicationState {
         // Reset time controller
         self.time_controller.reset();
 
-        // Reset game state
-        self.game_state.reset(&mut self.rng);
+        // Reset game state, re-seeding its play bounds from the current (possibly
+        // slider-adjusted) window size
+        self.game_state.reset(self.game_size, &mut self.rng);
 
         self.event_buffer.push(Event::GameStart);
     }
+
+    /// Builds the pause menu overlay, feeding any changes back into the relevant controllers
+    fn build_pause_menu(&mut self, ctx: &mut Context) {
+        let egui_ctx = self.egui_backend.ctx();
+        egui::Window::new("Paused").show(&egui_ctx, |ui| {
+            if ui.button("Resume").clicked() {
+                self.paused = false;
+            }
+            if ui.button("Restart").clicked() {
+                self.reset();
+                self.paused = false;
+            }
+            if ui.button("Quit").clicked() {
+                ctx.request_quit();
+            }
+
+            ui.separator();
+
+            ui.label("Master volume");
+            ui.add(egui::Slider::new(&mut self.master_volume, 0.0..=1.0));
+
+            ui.label("Window size");
+            let mut width = self.game_size.width;
+            let mut height = self.game_size.height;
+            ui.add(egui::Slider::new(&mut width, 320.0..=1920.0).text("width"));
+            ui.add(egui::Slider::new(&mut height, 240.0..=1080.0).text("height"));
+            if width != self.game_size.width || height != self.game_size.height {
+                self.game_size = Size::new(width, height);
+                let _ = ctx.gfx.set_drawable_size(width, height);
+                // Keep the simulation's play bounds in sync with the window instead of only
+                // picking up the new size on the next restart
+                self.game_state.resize(self.game_size);
+            }
+        });
+    }
 }
 
 // We implement `ggez::event::EventHandler` trait on our application state - this is where we can
@@ -80,29 +159,81 @@ impl event::EventHandler for ApplicationState {
             return Ok(());
         }
 
-        // Update game state, and check for collisions
-        let duration = ctx.time.delta();
-        self.time_controller.update_seconds(
-            duration,
-            self.input_controller.actions(),
-            &mut self.game_state,
-            &mut self.event_buffer,
-            &mut self.rng,
-        );
+        self.egui_backend.update(ctx);
 
-        CollisionsController::handle_collisions(
-            &mut self.game_state,
-            &mut self.time_controller,
-            &mut self.event_buffer,
-        );
+        // While the pause menu is open, the simulation is frozen
+        if self.paused {
+            self.build_pause_menu(ctx);
+            return Ok(());
+        }
+
+        // Gather this frame's actions and delta, either from live input or from a replay. When
+        // replaying, the recorded actions and delta replace live input entirely so the run
+        // reproduces exactly.
+        let (actions, delta_secs) = if let Some(player) = &mut self.player {
+            match player.next_frame() {
+                Some(frame) => (frame.actions, frame.delta_secs),
+                None => return Ok(()),
+            }
+        } else {
+            let duration = ctx.time.delta();
+            let actions = self.input_controller.actions();
+            if let Some(recorder) = &mut self.recorder {
+                recorder.record(actions, duration.as_secs_f64());
+            }
+            (actions, duration.as_secs_f64())
+        };
+
+        // Feed the accumulator and run as many fixed steps as it can afford, clamped so a slow
+        // frame doesn't spiral into running ever more catch-up steps
+        self.time_controller.accumulate(delta_secs);
+        let mut catchup_steps = 0;
+        while self.time_controller.accumulator() >= FIXED_TIMESTEP_SECONDS
+            && catchup_steps < MAX_CATCHUP_STEPS
+        {
+            self.time_controller.step(
+                FIXED_TIMESTEP_SECONDS,
+                actions,
+                &mut self.game_state,
+                &mut self.event_buffer,
+                &mut self.rng,
+            );
+
+            CollisionsController::handle_collisions(
+                &mut self.game_state,
+                &mut self.time_controller,
+                &mut self.event_buffer,
+            );
+
+            catchup_steps += 1;
+        }
+
+        // Cap whatever backlog the catch-up cap left behind at `MAX_CATCHUP_STEPS` fixed steps,
+        // so a sustained slow frame doesn't leave the sim further and further behind real time
+        self.time_controller.clamp_accumulator(FIXED_TIMESTEP_SECONDS, MAX_CATCHUP_STEPS);
 
         Ok(())
     }
 
     // This is called when ggez wants us to draw our game
     fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
-        view::play_sounds(ctx, &mut self.event_buffer, &mut self.resources)?;
-        view::render_game(self, ctx)
+        // `play_sounds` pans/attenuates each event's sound by the position the event carries,
+        // relative to the screen center of `game_state`, and draws a randomized pitch
+        // offset from `rng` so repeated sounds don't sound mechanically identical
+        view::play_sounds(
+            ctx,
+            &mut self.event_buffer,
+            &mut self.resources,
+            &self.game_state,
+            self.master_volume,
+            &mut self.rng,
+        )?;
+        // The accumulator's leftover fraction lets `render_game` interpolate each model between
+        // its previous and current transform, so motion stays smooth regardless of refresh rate
+        let interpolation_alpha = self.time_controller.interpolation_alpha(FIXED_TIMESTEP_SECONDS);
+        view::render_game(self, ctx, interpolation_alpha)?;
+        self.egui_backend.draw(ctx);
+        Ok(())
     }
 
     // Listen for keyboard events
@@ -112,6 +243,18 @@ impl event::EventHandler for ApplicationState {
         input: KeyInput,
         _repeated: bool,
     ) -> GameResult {
+        // Escape toggles the pause menu instead of going through the message/reset flow; this
+        // has to run even while paused, since it's the only way to unpause
+        if input.keycode == Some(KeyCode::Escape) {
+            self.paused = !self.paused;
+            return Ok(());
+        }
+
+        // While the pause menu is open, keys are consumed by the overlay, not gameplay
+        if self.paused {
+            return Ok(());
+        }
+
         // If we're displaying a message (waiting for user input) then hide it and reset the game
         if let Some(_) = self.game_state.message {
             self.reset();
@@ -121,15 +264,135 @@ impl event::EventHandler for ApplicationState {
     }
 
     fn key_up_event(&mut self, _ctx: &mut Context, input: KeyInput) -> GameResult {
+        if self.paused {
+            return Ok(());
+        }
         self.input_controller.key_release(input);
         Ok(())
     }
 
+    // Track the cursor so the player ship can steer toward the desired heading it implies
+    fn mouse_motion_event(
+        &mut self,
+        _ctx: &mut Context,
+        x: f32,
+        y: f32,
+        _dx: f32,
+        _dy: f32,
+    ) -> GameResult {
+        // While the pause menu is open, the cursor drives the overlay, not the ship
+        if self.paused {
+            return Ok(());
+        }
+        self.input_controller.set_cursor_position(x, y);
+        Ok(())
+    }
+
+    // The left mouse button fires, aiming at wherever the cursor currently is
+    fn mouse_button_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> GameResult {
+        // While the pause menu is open, clicks are consumed by the overlay (resume/restart/quit
+        // buttons, sliders), not gameplay
+        if self.paused {
+            return Ok(());
+        }
+
+        // If we're displaying a message (waiting for user input) then hide it and reset the game
+        if let Some(_) = self.game_state.message {
+            self.reset();
+        }
+        self.input_controller.set_cursor_position(x, y);
+        self.input_controller.mouse_button_press(button);
+        Ok(())
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        _x: f32,
+        _y: f32,
+    ) -> GameResult {
+        if self.paused {
+            return Ok(());
+        }
+        self.input_controller.mouse_button_release(button);
+        Ok(())
+    }
+
+    // Listen for gamepad button presses so the game is playable without a keyboard
+    fn gamepad_button_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        btn: Button,
+        _id: GamepadId,
+    ) -> GameResult {
+        // While the pause menu is open, gamepad buttons are consumed by the overlay, not
+        // gameplay
+        if self.paused {
+            return Ok(());
+        }
+
+        if let Some(_) = self.game_state.message {
+            self.reset();
+        }
+        self.input_controller.gamepad_button_press(btn);
+        Ok(())
+    }
+
+    fn gamepad_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        btn: Button,
+        _id: GamepadId,
+    ) -> GameResult {
+        if self.paused {
+            return Ok(());
+        }
+        self.input_controller.gamepad_button_release(btn);
+        Ok(())
+    }
+
+    // Analog stick movement is translated into the same proportional turn actions the
+    // keyboard's discrete turn-left/turn-right keys produce
+    fn gamepad_axis_event(
+        &mut self,
+        _ctx: &mut Context,
+        axis: Axis,
+        value: f32,
+        _id: GamepadId,
+    ) -> GameResult {
+        if self.paused {
+            return Ok(());
+        }
+
+        let value = if value.abs() < GAMEPAD_AXIS_DEADZONE {
+            0.0
+        } else {
+            value
+        };
+        self.input_controller.gamepad_axis(axis, value);
+        Ok(())
+    }
+
     // Listen for window focus to pause the game's execution
     fn focus_event(&mut self, _ctx: &mut Context, has_focus: bool) -> GameResult {
         self.has_focus = has_focus;
         Ok(())
     }
+
+    // Flush any recorded frames to disk before the game closes
+    fn quit_event(&mut self, _ctx: &mut Context) -> GameResult<bool> {
+        if let Some(recorder) = &self.recorder {
+            recorder.save()?;
+        }
+        Ok(false)
+    }
 }
 
 #[derive(StructOpt, Debug)]
@@ -142,6 +405,19 @@ struct Opt {
     /// Window height
     #[structopt(long = "height", default_value = "576")]
     height: usize,
+
+    /// Seed for the random number generator driving the simulation; runs with the same seed
+    /// and the same recorded/live input evolve identically
+    #[structopt(long = "seed", default_value = "0")]
+    seed: u64,
+
+    /// Record this run's input stream to the given file, for later replay
+    #[structopt(long = "record")]
+    record: Option<PathBuf>,
+
+    /// Replay a previously recorded input stream from the given file instead of live input
+    #[structopt(long = "replay")]
+    replay: Option<PathBuf>,
 }
 
 fn main() {
@@ -151,7 +427,12 @@ fn main() {
     // Create the rendering context and set the background color to black
     let (mut ctx, event_loop) = view::init_rendering_ctx(game_size).unwrap();
 
+    let recorder = opt.record.map(Recorder::new);
+    let player = opt
+        .replay
+        .map(|path| Player::load(&path).unwrap());
+
     // Load the application state and start the event loop
-    let state = ApplicationState::new(&mut ctx, game_size).unwrap();
+    let state = ApplicationState::new(&mut ctx, game_size, opt.seed, recorder, player).unwrap();
     event::run(ctx, event_loop, state);
 }
@@ -0,0 +1,116 @@
+//! Data types describing the entities that make up the game world, plus enough state on each
+//! to let the view interpolate motion between fixed simulation steps.
+
+use rand::Rng;
+
+use geometry::Size;
+
+/// A position and rotation in game space.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Transform {
+    /// World-space position
+    pub position: (f32, f32),
+    /// Rotation, in radians
+    pub rotation: f32,
+}
+
+/// Keeps an entity's previous and current transform so the view can interpolate between them
+/// when drawing, independent of the simulation's fixed step rate.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Model {
+    previous: Transform,
+    current: Transform,
+}
+
+impl Model {
+    /// Creates a model with both transforms set to the same starting point
+    pub fn new(transform: Transform) -> Model {
+        Model {
+            previous: transform,
+            current: transform,
+        }
+    }
+
+    /// Moves `current` into `previous` and sets a new `current`; called once per fixed step
+    pub fn advance(&mut self, transform: Transform) {
+        self.previous = self.current;
+        self.current = transform;
+    }
+
+    /// The transform as of the most recent fixed step
+    pub fn current(&self) -> Transform {
+        self.current
+    }
+
+    /// Linearly interpolates between the previous and current transform by `alpha` (expected
+    /// to be in `0.0..=1.0`)
+    pub fn interpolated(&self, alpha: f32) -> Transform {
+        Transform {
+            position: (
+                self.previous.position.0 + (self.current.position.0 - self.previous.position.0) * alpha,
+                self.previous.position.1 + (self.current.position.1 - self.previous.position.1) * alpha,
+            ),
+            rotation: self.previous.rotation + (self.current.rotation - self.previous.rotation) * alpha,
+        }
+    }
+}
+
+/// The player-controlled rocket
+pub struct Player {
+    /// Its visual/physical transform, previous and current
+    pub model: Model,
+    /// Current velocity
+    pub velocity: (f32, f32),
+}
+
+impl Player {
+    /// Spawns the player at the center of the play area, at rest
+    pub fn spawn(size: Size) -> Player {
+        Player {
+            model: Model::new(Transform {
+                position: (size.width / 2.0, size.height / 2.0),
+                rotation: 0.0,
+            }),
+            velocity: (0.0, 0.0),
+        }
+    }
+}
+
+/// A drifting hazard the player must avoid or shoot
+pub struct Asteroid {
+    /// Its visual/physical transform, previous and current
+    pub model: Model,
+    /// Current velocity
+    pub velocity: (f32, f32),
+}
+
+impl Asteroid {
+    /// Spawns `count` asteroids at random positions/velocities within the play area
+    pub fn spawn_many<R: Rng>(count: usize, size: Size, rng: &mut R) -> Vec<Asteroid> {
+        (0..count)
+            .map(|_| {
+                let position = (
+                    rng.gen_range(0.0..size.width),
+                    rng.gen_range(0.0..size.height),
+                );
+                let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+                let speed = rng.gen_range(20.0..60.0);
+                Asteroid {
+                    model: Model::new(Transform {
+                        position,
+                        rotation: angle,
+                    }),
+                    velocity: (angle.cos() * speed, angle.sin() * speed),
+                }
+            })
+            .collect()
+    }
+}
+
+/// A projectile fired by the player
+pub struct Bullet {
+    /// Its visual/physical transform, previous and current
+    pub model: Model,
+    /// Current velocity
+    pub velocity: (f32, f32),
+}
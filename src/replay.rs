@@ -0,0 +1,75 @@
+//! Recording and replaying of the per-frame input stream.
+//!
+//! Combined with a seeded `rng`, replaying the exact sequence of actions and deltas that were
+//! recorded during a run reproduces that run's `GameState` evolution identically. This turns bug
+//! reports into exact reproductions and enables demo playback.
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::controllers::Actions;
+
+/// A single frame of recorded input: the actions that were active and how much time passed.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RecordedFrame {
+    /// The actions that were active during this frame
+    pub actions: Actions,
+    /// The frame's delta time, in seconds
+    pub delta_secs: f64,
+}
+
+/// Appends frames to a file as the game runs, so the run can be replayed later
+pub struct Recorder {
+    path: std::path::PathBuf,
+    frames: Vec<RecordedFrame>,
+}
+
+impl Recorder {
+    /// Creates a recorder that will write `path` once the run ends
+    pub fn new(path: impl Into<std::path::PathBuf>) -> Recorder {
+        Recorder {
+            path: path.into(),
+            frames: Vec::new(),
+        }
+    }
+
+    /// Records a single frame's worth of actions and delta time
+    pub fn record(&mut self, actions: Actions, delta_secs: f64) {
+        self.frames.push(RecordedFrame {
+            actions,
+            delta_secs,
+        });
+    }
+
+    /// Flushes all recorded frames to disk as JSON
+    pub fn save(&self) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(&self.path)?);
+        serde_json::to_writer(writer, &self.frames)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+    }
+}
+
+/// Reads back a previously recorded frame stream and plays it one frame at a time
+pub struct Player {
+    frames: std::vec::IntoIter<RecordedFrame>,
+}
+
+impl Player {
+    /// Loads a recorded frame stream from `path`
+    pub fn load(path: &Path) -> io::Result<Player> {
+        let reader = BufReader::new(File::open(path)?);
+        let frames: Vec<RecordedFrame> = serde_json::from_reader(reader)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        Ok(Player {
+            frames: frames.into_iter(),
+        })
+    }
+
+    /// Returns the next recorded frame, or `None` once the recording is exhausted
+    pub fn next_frame(&mut self) -> Option<RecordedFrame> {
+        self.frames.next()
+    }
+}
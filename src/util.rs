@@ -0,0 +1,11 @@
+//! Small math helpers shared across modules.
+
+/// Wraps `value` into `0..max`, for a toroidal play area
+pub fn wrap(value: f32, max: f32) -> f32 {
+    let wrapped = value % max;
+    if wrapped < 0.0 {
+        wrapped + max
+    } else {
+        wrapped
+    }
+}
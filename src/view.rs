@@ -0,0 +1,150 @@
+//! Rendering and audio playback for the game.
+
+use ggez::audio::{SoundSource, SpatialSource};
+use ggez::conf::WindowMode;
+use ggez::event::EventLoop;
+use ggez::graphics::{self, Canvas, Color, DrawMode, DrawParam, Text};
+use ggez::{Context, ContextBuilder, GameResult};
+use rand::Rng;
+
+use geometry::Size;
+
+use crate::controllers::Event;
+use crate::game_state::GameState;
+use crate::ApplicationState;
+
+// Small per-play pitch jitter so repeated shots/explosions don't sound mechanically identical
+const PITCH_JITTER: f32 = 0.08;
+// Concurrent explosion voices; round-robined so overlapping explosions don't cut each other off
+const EXPLOSION_VOICES: usize = 4;
+
+/// The loaded sounds used throughout the game
+pub struct Resources {
+    shot_sound: SpatialSource,
+    explosion_sounds: Vec<SpatialSource>,
+    next_explosion_voice: usize,
+}
+
+impl Resources {
+    /// Loads all resources up front
+    pub fn new(ctx: &mut Context) -> Resources {
+        Resources {
+            shot_sound: SpatialSource::new(ctx, "/shot.ogg").expect("failed to load shot sound"),
+            explosion_sounds: (0..EXPLOSION_VOICES)
+                .map(|_| {
+                    SpatialSource::new(ctx, "/explosion.ogg")
+                        .expect("failed to load explosion sound")
+                })
+                .collect(),
+            next_explosion_voice: 0,
+        }
+    }
+
+    fn next_explosion_voice(&mut self) -> &mut SpatialSource {
+        let voice = &mut self.explosion_sounds[self.next_explosion_voice];
+        self.next_explosion_voice = (self.next_explosion_voice + 1) % self.explosion_sounds.len();
+        voice
+    }
+}
+
+/// Creates the ggez rendering context and event loop for a window of the given size
+pub fn init_rendering_ctx(size: Size) -> GameResult<(Context, EventLoop<()>)> {
+    ContextBuilder::new("rocket", "nobbele")
+        .window_mode(WindowMode::default().dimensions(size.width, size.height))
+        .build()
+}
+
+/// Drains `event_buffer`, playing each event's sound panned/attenuated by the emitting entity's
+/// position relative to screen center, with a small randomized pitch offset drawn from `rng` so
+/// repeated sounds (shots, explosions) don't sound mechanically identical. Concurrent
+/// explosions are spread across a small pool of voices so they don't cut each other off.
+pub fn play_sounds<R: Rng>(
+    ctx: &mut Context,
+    event_buffer: &mut Vec<Event>,
+    resources: &mut Resources,
+    game_state: &GameState,
+    master_volume: f32,
+    rng: &mut R,
+) -> GameResult<()> {
+    let screen_center = (game_state.size.width / 2.0, game_state.size.height / 2.0);
+
+    for event in event_buffer.drain(..) {
+        let position = match event.position() {
+            Some(position) => position,
+            None => continue,
+        };
+
+        let source = match event {
+            Event::Shot { .. } => &mut resources.shot_sound,
+            Event::Explosion { .. } | Event::PlayerDeath { .. } => {
+                resources.next_explosion_voice()
+            }
+            Event::GameStart => continue,
+        };
+
+        source.set_position([position.0 - screen_center.0, position.1 - screen_center.1, 0.0]);
+        source.set_pitch(1.0 + rng.gen_range(-PITCH_JITTER..=PITCH_JITTER));
+        source.set_volume(master_volume);
+        source.play_detached(ctx)?;
+    }
+    Ok(())
+}
+
+/// Draws the current game world, blending each model's previous and current transform by
+/// `interpolation_alpha` so motion stays smooth independent of the display's refresh rate
+pub fn render_game(
+    app_state: &ApplicationState,
+    ctx: &mut Context,
+    interpolation_alpha: f32,
+) -> GameResult<()> {
+    let mut canvas = Canvas::from_frame(ctx, Color::BLACK);
+    let game_state = &app_state.game_state;
+
+    let player_mesh = graphics::Mesh::new_circle(
+        ctx,
+        DrawMode::fill(),
+        [0.0, 0.0],
+        16.0,
+        0.5,
+        Color::WHITE,
+    )?;
+    let player_transform = game_state.player.model.interpolated(interpolation_alpha);
+    canvas.draw(
+        &player_mesh,
+        DrawParam::default()
+            .dest([player_transform.position.0, player_transform.position.1])
+            .rotation(player_transform.rotation),
+    );
+
+    let asteroid_mesh =
+        graphics::Mesh::new_circle(ctx, DrawMode::fill(), [0.0, 0.0], 24.0, 0.5, Color::from_rgb(150, 150, 150))?;
+    for asteroid in &game_state.asteroids {
+        let transform = asteroid.model.interpolated(interpolation_alpha);
+        canvas.draw(
+            &asteroid_mesh,
+            DrawParam::default().dest([transform.position.0, transform.position.1]),
+        );
+    }
+
+    let bullet_mesh =
+        graphics::Mesh::new_circle(ctx, DrawMode::fill(), [0.0, 0.0], 4.0, 0.5, Color::YELLOW)?;
+    for bullet in &game_state.bullets {
+        let transform = bullet.model.interpolated(interpolation_alpha);
+        canvas.draw(
+            &bullet_mesh,
+            DrawParam::default().dest([transform.position.0, transform.position.1]),
+        );
+    }
+
+    if let Some(message) = &game_state.message {
+        canvas.draw(
+            &Text::new(message.as_str()),
+            DrawParam::default().dest([
+                game_state.size.width / 2.0 - 100.0,
+                game_state.size.height / 2.0,
+            ]),
+        );
+    }
+
+    canvas.finish(ctx)
+}